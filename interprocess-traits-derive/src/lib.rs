@@ -0,0 +1,64 @@
+//! Derive macros for `interprocess-traits`.
+//!
+//! `ProcSend`/`ProcSync` are plain (non-auto) traits unless the `auto-traits` feature is
+//! enabled, so implementing them by hand means one `unsafe impl` per type. `#[derive(ProcSend)]`
+//! and `#[derive(ProcSync)]` generate that impl instead: every field (or, for an enum, every
+//! field of every variant) adds a `where Field: Trait` bound, so the derived impl only holds
+//! once all of the type's fields do. Mark a field `#[proc_unsafe_assume]` to drop it from that
+//! bound when you're vouching for its safety yourself.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput};
+
+#[proc_macro_derive(ProcSend, attributes(proc_unsafe_assume))]
+pub fn derive_proc_send(input: TokenStream) -> TokenStream {
+    derive_marker(input, quote!(::interprocess_traits::ProcSend))
+}
+
+#[proc_macro_derive(ProcSync, attributes(proc_unsafe_assume))]
+pub fn derive_proc_sync(input: TokenStream) -> TokenStream {
+    derive_marker(input, quote!(::interprocess_traits::ProcSync))
+}
+
+fn derive_marker(input: TokenStream, trait_path: proc_macro2::TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident.clone();
+    let field_types = field_types(&input.data);
+
+    // Splice into the existing `Punctuated` list rather than token-pasting, so this works
+    // whether or not the type already has a `where` clause (and regardless of whether that
+    // clause ends in a trailing comma).
+    if !field_types.is_empty() {
+        let where_clause = input.generics.make_where_clause();
+        for ty in &field_types {
+            where_clause.predicates.push(parse_quote!(#ty: #trait_path));
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let expanded = quote! {
+        unsafe impl #impl_generics #trait_path for #name #ty_generics #where_clause {}
+    };
+    expanded.into()
+}
+
+fn field_types(data: &Data) -> Vec<syn::Type> {
+    let fields: Box<dyn Iterator<Item = &syn::Field>> = match data {
+        Data::Struct(data) => Box::new(data.fields.iter()),
+        Data::Enum(data) => Box::new(data.variants.iter().flat_map(|variant| variant.fields.iter())),
+        Data::Union(data) => Box::new(data.fields.named.iter()),
+    };
+
+    fields
+        .filter(|field| !has_assume_attr(&field.attrs))
+        .map(|field| field.ty.clone())
+        .collect()
+}
+
+fn has_assume_attr(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| attr.path().is_ident("proc_unsafe_assume"))
+}
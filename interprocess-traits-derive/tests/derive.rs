@@ -0,0 +1,53 @@
+use interprocess_traits::{ProcSend, ProcSync};
+use interprocess_traits_derive::{ProcSend, ProcSync};
+
+fn assert_proc_send<T: ProcSend>() {}
+fn assert_proc_sync<T: ProcSync>() {}
+
+#[derive(ProcSend, ProcSync)]
+struct Plain {
+    a: u32,
+    b: bool,
+}
+
+#[derive(ProcSend, ProcSync)]
+struct Generic<T>
+where
+    T: Clone,
+{
+    value: T,
+}
+
+#[derive(ProcSend, ProcSync)]
+enum Either<A, B> {
+    Left(A),
+    Right(B),
+}
+
+#[derive(ProcSend, ProcSync)]
+union Bits {
+    word: u32,
+}
+
+struct NotProcAnnotated;
+
+#[derive(ProcSend, ProcSync)]
+struct Assumed {
+    tracked: u32,
+    #[proc_unsafe_assume]
+    untracked: NotProcAnnotated,
+}
+
+#[test]
+fn derives_compile_and_implement_the_traits() {
+    assert_proc_send::<Plain>();
+    assert_proc_sync::<Plain>();
+    assert_proc_send::<Generic<u32>>();
+    assert_proc_sync::<Generic<u32>>();
+    assert_proc_send::<Either<u32, bool>>();
+    assert_proc_sync::<Either<u32, bool>>();
+    assert_proc_send::<Bits>();
+    assert_proc_sync::<Bits>();
+    assert_proc_send::<Assumed>();
+    assert_proc_sync::<Assumed>();
+}
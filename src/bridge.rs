@@ -0,0 +1,163 @@
+//! Bridging wrappers between the ordinary [`Send`]/[`Sync`] world and the `Proc*` world.
+//!
+//! These mirror the `FromDyn`/`IntoDynSyncSend` pattern used elsewhere to cross an
+//! otherwise-unbridgeable trait boundary: one wrapper is an explicit escape hatch for
+//! asserting a type is process-safe, the other is a checked way to hand a `Proc*` value
+//! to code that only understands `Send`/`Sync`.
+
+use core::ops::Deref;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{ProcSend, ProcSync};
+
+static MULTIPROCESS: AtomicBool = AtomicBool::new(false);
+
+/// Marks the current process as participating in a multiprocess transport.
+///
+/// [`FromProc::new`] panics unless the most recent call to this passed `true`, so that
+/// the "this value actually came from / is going to another process" assertion it encodes
+/// cannot be bypassed by accident.
+pub fn set_multiprocess(multiprocess: bool) {
+    MULTIPROCESS.store(multiprocess, Ordering::SeqCst);
+}
+
+/// Returns whether [`set_multiprocess`] has most recently been called with `true`.
+pub fn is_multiprocess() -> bool {
+    MULTIPROCESS.load(Ordering::SeqCst)
+}
+
+/// An explicit escape hatch: wraps a `T: Send + Sync` and asserts it is also `ProcSend`
+/// and `ProcSync`, without requiring the crate to have annotated `T` itself.
+///
+/// This is for values the caller has manually verified are safe to transfer across a
+/// process boundary (e.g. because they only contain indices, not pointers) but which
+/// can't or don't implement [`ProcSend`]/[`ProcSync`] directly.
+pub struct IntoProcSyncSend<T: Send + Sync>(T);
+
+impl<T: Send + Sync> IntoProcSyncSend<T> {
+    /// Wraps `value`, asserting that it is safe to treat as [`ProcSend`] and [`ProcSync`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure `value` does not contain anything that is meaningless or
+    /// unsound to interpret in another process' address space.
+    pub unsafe fn new(value: T) -> Self {
+        IntoProcSyncSend(value)
+    }
+
+    /// Unwraps back into the underlying value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Send + Sync> Deref for IntoProcSyncSend<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+// SAFETY: callers of `IntoProcSyncSend::new` have asserted `T` is process-safe.
+unsafe impl<T: Send + Sync> ProcSend for IntoProcSyncSend<T> {}
+// SAFETY: callers of `IntoProcSyncSend::new` have asserted `T` is process-safe.
+unsafe impl<T: Send + Sync> ProcSync for IntoProcSyncSend<T> {}
+
+/// A checked way to move a `ProcSend`/`ProcSync` value through code that only knows about
+/// the ordinary, thread-based [`Send`]/[`Sync`] traits.
+///
+/// Its `Send`/`Sync` impls also require `T: Send`/`T: Sync`, so this is sound on its own; what [`FromProc::new`] additionally guards against is using it as a
+/// laundering path when no multiprocess transport is actually in play, by requiring
+/// [`set_multiprocess`] to have been enabled first.
+pub struct FromProc<T>(T);
+
+impl<T> FromProc<T> {
+    fn new_checked(value: T) -> Self {
+        assert!(
+            is_multiprocess(),
+            "FromProc::new requires multiprocess mode to be enabled via set_multiprocess(true)"
+        );
+        FromProc(value)
+    }
+
+    /// Unwraps back into the underlying value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: ProcSend> FromProc<T> {
+    /// Wraps a [`ProcSend`] `value`, panicking if [`is_multiprocess`] is `false`.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless [`set_multiprocess`] was most recently called with `true`.
+    pub fn new(value: T) -> Self {
+        Self::new_checked(value)
+    }
+}
+
+impl<T: ProcSync> FromProc<T> {
+    /// Wraps a [`ProcSync`]-only `value` (one that isn't necessarily [`ProcSend`]),
+    /// panicking if [`is_multiprocess`] is `false`.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless [`set_multiprocess`] was most recently called with `true`.
+    pub fn new_sync(value: T) -> Self {
+        Self::new_checked(value)
+    }
+}
+
+impl<T> Deref for FromProc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+// SAFETY: `T` is `Send` (required explicitly, since with the `auto-traits` feature
+// `ProcSend` has no `Send` supertrait), and `FromProc::new` asserts the value is
+// actually being used in a multiprocess context.
+unsafe impl<T: ProcSend + Send> Send for FromProc<T> {}
+// SAFETY: `T` is `Sync` (required explicitly, since with the `auto-traits` feature
+// `ProcSync` has no `Sync` supertrait), and `FromProc::new`/`new_sync` assert the value
+// is actually being used in a multiprocess context.
+unsafe impl<T: ProcSync + Sync> Sync for FromProc<T> {}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+
+    struct SendOnly(u32);
+    unsafe impl ProcSend for SendOnly {}
+
+    struct SyncOnly(u32);
+    unsafe impl ProcSync for SyncOnly {}
+
+    #[test]
+    fn new_panics_until_multiprocess_is_enabled_then_round_trips() {
+        let before_enabling = std::panic::catch_unwind(|| FromProc::new(SendOnly(1)));
+        assert!(before_enabling.is_err());
+
+        set_multiprocess(true);
+
+        let wrapped = FromProc::new(SendOnly(7));
+        assert_eq!(wrapped.0, 7);
+        assert_eq!(wrapped.into_inner().0, 7);
+
+        let wrapped_sync = FromProc::new_sync(SyncOnly(9));
+        assert_eq!(wrapped_sync.0, 9);
+    }
+
+    #[test]
+    fn into_proc_sync_send_round_trips() {
+        let wrapped = unsafe { IntoProcSyncSend::new(5i32) };
+        assert_eq!(*wrapped, 5);
+        assert_eq!(wrapped.into_inner(), 5);
+    }
+}
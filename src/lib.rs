@@ -1,4 +1,4 @@
-#![cfg_attr(feature="auto-traits", feature(optin_builtin_traits))]
+#![cfg_attr(feature="auto-traits", feature(optin_builtin_traits, allocator_api))]
 #![cfg_attr(not(feature="std"), no_std)]
 //! The interprocess-traits crate provides type traits to annotate types which have certain
 //! properties when used in a multiprocess environment.
@@ -7,9 +7,27 @@
 #[cfg(feature="std")]
 use std as core;
 
+#[cfg(feature="auto-traits")]
+extern crate alloc;
+
 use core::sync::atomic::*;
 use core::num::*;
 
+mod bridge;
+pub use bridge::{FromProc, IntoProcSyncSend, is_multiprocess, set_multiprocess};
+
+mod ptr;
+pub use ptr::{ProcPtr, ResolveError, SharedRegion};
+
+/// Derives `unsafe impl ProcSend`/`unsafe impl ProcSync` by requiring every field (or, for
+/// enums, every field of every variant) to implement the trait being derived. Annotate a
+/// field with `#[proc_unsafe_assume]` to omit it from the generated bound when you take
+/// manual responsibility for its safety instead.
+///
+/// Requires the `derive` feature.
+#[cfg(feature="derive")]
+pub use interprocess_traits_derive::{ProcSend, ProcSync};
+
 #[cfg(feature="auto-traits")]
 macro_rules! maybe_auto_trait {
     ($doctext:literal pub unsafe trait $traitname:ident: $deps:ident {}) => {
@@ -90,16 +108,36 @@ unsafe impl<T: ProcSend> ProcSend for core::cell::Cell<T> {}
 unsafe impl<T: ProcSend> ProcSend for core::cell::UnsafeCell<T> {}
 unsafe impl<T: ProcSend> ProcSend for core::mem::ManuallyDrop<T> {}
 
+// Composite types: the marker propagates through structure the same way Send/Sync do.
+unsafe impl<T: ProcSend, const N: usize> ProcSend for [T; N] {}
+unsafe impl<T: ProcSend> ProcSend for [T] {}
+unsafe impl<T: ProcSend> ProcSend for Option<T> {}
+unsafe impl<T: ProcSend, E: ProcSend> ProcSend for Result<T, E> {}
+unsafe impl<T: ?Sized + Send> ProcSend for core::marker::PhantomData<T> {}
+unsafe impl<T: ProcSend> ProcSend for core::num::Wrapping<T> {}
+unsafe impl<T: ProcSend> ProcSend for core::cmp::Reverse<T> {}
+unsafe impl<T: ProcSend> ProcSend for core::mem::MaybeUninit<T> {}
+
 #[cfg(feature="auto-traits")]
-impl<T> !ProcSend for *const T {}
+impl<T: ?Sized> !ProcSend for *const T {}
 #[cfg(feature="auto-traits")]
-impl<T> !ProcSend for *mut T {}
+impl<T: ?Sized> !ProcSend for *mut T {}
 #[cfg(feature="auto-traits")]
 impl<T> !ProcSend for AtomicPtr<T> {}
 #[cfg(feature="auto-traits")]
-impl<T> !ProcSend for &T {}
+impl<T: ?Sized> !ProcSend for &T {}
+#[cfg(feature="auto-traits")]
+impl<T: ?Sized> !ProcSend for &mut T {}
+#[cfg(feature="auto-traits")]
+impl<T: ?Sized, A: core::alloc::Allocator> !ProcSend for alloc::rc::Rc<T, A> {}
 #[cfg(feature="auto-traits")]
-impl<T> !ProcSend for &mut T {}
+impl<T: ?Sized, A: core::alloc::Allocator> !ProcSend for alloc::rc::Weak<T, A> {}
+#[cfg(feature="auto-traits")]
+impl<T: ?Sized> !ProcSend for core::ptr::NonNull<T> {}
+#[cfg(all(feature="auto-traits", feature="std"))]
+impl !ProcSend for std::env::Args {}
+#[cfg(all(feature="auto-traits", feature="std"))]
+impl !ProcSend for std::env::ArgsOs {}
 #[cfg(all(feature="auto-traits", feature="std"))]
 impl !ProcSend for std::net::TcpListener {}
 #[cfg(all(feature="auto-traits", feature="std"))]
@@ -144,22 +182,53 @@ implement_marker_for! {
 
 unsafe impl<T: ProcSync> ProcSync for core::mem::ManuallyDrop<T> {}
 
+// Composite types: the marker propagates through structure the same way Send/Sync do.
+unsafe impl<T: ProcSync, const N: usize> ProcSync for [T; N] {}
+unsafe impl<T: ProcSync> ProcSync for [T] {}
+unsafe impl<T: ProcSync> ProcSync for Option<T> {}
+unsafe impl<T: ProcSync, E: ProcSync> ProcSync for Result<T, E> {}
+unsafe impl<T: ?Sized + Sync> ProcSync for core::marker::PhantomData<T> {}
+unsafe impl<T: ProcSync> ProcSync for core::num::Wrapping<T> {}
+unsafe impl<T: ProcSync> ProcSync for core::cmp::Reverse<T> {}
+unsafe impl<T: ProcSync> ProcSync for core::mem::MaybeUninit<T> {}
+
+// Tuples up to arity 12, recursively peeling off the first element so that e.g.
+// `(A, B, C)` requires `A: $trait, B: $trait, C: $trait` just like the shorter tuples do.
+macro_rules! implement_marker_for_tuples {
+    ($trait:ident;) => {};
+    ($trait:ident; $head:ident $(, $tail:ident)*) => {
+        unsafe impl<$head: $trait $(, $tail: $trait)*> $trait for ($head, $($tail,)*) {}
+        implement_marker_for_tuples!($trait; $($tail),*);
+    };
+}
+
+implement_marker_for_tuples!(ProcSend; A, B, C, D, E, F, G, H, I, J, K, L);
+implement_marker_for_tuples!(ProcSync; A, B, C, D, E, F, G, H, I, J, K, L);
+
 #[cfg(feature="auto-traits")]
 impl<T> !ProcSync for core::cell::Cell<T> {}
 #[cfg(feature="auto-traits")]
 impl<T> !ProcSync for core::cell::UnsafeCell<T> {}
 #[cfg(feature="auto-traits")]
-impl<T> !ProcSync for *const T {}
+impl<T: ?Sized> !ProcSync for *const T {}
 #[cfg(feature="auto-traits")]
-impl<T> !ProcSync for *mut T {}
+impl<T: ?Sized> !ProcSync for *mut T {}
 #[cfg(feature="auto-traits")]
 impl<T> !ProcSync for AtomicPtr<T> {}
 #[cfg(feature="auto-traits")]
-impl<T> !ProcSync for core::ptr::NonNull<T> {}
+impl<T: ?Sized> !ProcSync for core::ptr::NonNull<T> {}
+#[cfg(feature="auto-traits")]
+impl<T: ?Sized> !ProcSync for &T {}
+#[cfg(feature="auto-traits")]
+impl<T: ?Sized> !ProcSync for &mut T {}
 #[cfg(feature="auto-traits")]
-impl<T> !ProcSync for &T {}
+impl<T: ?Sized, A: core::alloc::Allocator> !ProcSync for alloc::rc::Rc<T, A> {}
 #[cfg(feature="auto-traits")]
-impl<T> !ProcSync for &mut T {}
+impl<T: ?Sized, A: core::alloc::Allocator> !ProcSync for alloc::rc::Weak<T, A> {}
+#[cfg(all(feature="auto-traits", feature="std"))]
+impl !ProcSync for std::env::Args {}
+#[cfg(all(feature="auto-traits", feature="std"))]
+impl !ProcSync for std::env::ArgsOs {}
 #[cfg(all(feature="auto-traits", feature="std"))]
 impl !ProcSync for std::net::TcpListener {}
 #[cfg(all(feature="auto-traits", feature="std"))]
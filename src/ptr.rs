@@ -0,0 +1,189 @@
+//! A process-relative pointer, for the pattern the crate-level docs describe: "use indices
+//! which the processes would know how to convert into their own address space."
+//!
+//! [`ProcPtr<T>`] stores a byte offset relative to the start of a shared-memory mapping
+//! instead of an absolute address, so it is meaningful (and safe to transfer) in any
+//! process that has the same region mapped. [`SharedRegion`] records where that mapping
+//! lives locally and is the only way to turn a [`ProcPtr`] back into a usable reference.
+
+use core::marker::PhantomData;
+use core::mem;
+
+use crate::{ProcSend, ProcSync};
+
+/// A pointer to a `T` expressed as a byte offset into a [`SharedRegion`], rather than as
+/// an absolute address.
+///
+/// Because it carries no raw pointer, a `ProcPtr<T>` has no dependency on this process'
+/// address space and can be sent to, or shared with, another process that has mapped the
+/// same region.
+pub struct ProcPtr<T> {
+    offset: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ProcPtr<T> {
+    /// The byte offset this pointer refers to, relative to the start of its region.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<T> Clone for ProcPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ProcPtr<T> {}
+
+// SAFETY: a `ProcPtr<T>` contains only a `usize` offset, not a pointer into this
+// process' address space, so it carries no more than `T`'s own cross-process properties.
+unsafe impl<T: ProcSend> ProcSend for ProcPtr<T> {}
+// SAFETY: see above.
+unsafe impl<T: ProcSync> ProcSync for ProcPtr<T> {}
+
+/// An error returned by [`SharedRegion::resolve`] when a [`ProcPtr`] does not describe a
+/// valid, correctly-aligned `T` within the region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolveError {
+    /// `offset + size_of::<T>()` would read past the end of the region.
+    OutOfBounds,
+    /// The resolved address (region base plus offset) is not a multiple of `align_of::<T>()`.
+    Misaligned,
+}
+
+/// A handle to a mapped shared-memory region, recording its local base address and length.
+///
+/// `SharedRegion` does not itself map or unmap memory; it just describes a mapping the
+/// caller already holds, so that [`ProcPtr`]s can be minted and resolved against it.
+pub struct SharedRegion {
+    base: *mut u8,
+    len: usize,
+}
+
+impl SharedRegion {
+    /// Creates a handle for a region of `len` bytes starting at `base` in this process'
+    /// address space.
+    ///
+    /// # Safety
+    ///
+    /// `base` must be valid for reads and writes for `len` bytes for as long as this
+    /// `SharedRegion` (and any `ProcPtr`s resolved against it) are in use.
+    pub unsafe fn new(base: *mut u8, len: usize) -> Self {
+        SharedRegion { base, len }
+    }
+
+    /// The length, in bytes, of this region.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether this region is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Mints a [`ProcPtr`] pointing at `value`, which must live inside this region.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` does not point into this region.
+    pub fn offset_of<T>(&self, value: &T) -> ProcPtr<T> {
+        let value_addr = value as *const T as usize;
+        let base_addr = self.base as usize;
+        assert!(
+            value_addr >= base_addr && value_addr - base_addr + mem::size_of::<T>() <= self.len,
+            "value is not within this SharedRegion"
+        );
+        ProcPtr {
+            offset: value_addr - base_addr,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Resolves `ptr` into a raw pointer within this region, bounds- and alignment-checking
+    /// it first.
+    pub fn resolve<T>(&self, ptr: ProcPtr<T>) -> Result<*const T, ResolveError> {
+        let end = ptr
+            .offset
+            .checked_add(mem::size_of::<T>())
+            .ok_or(ResolveError::OutOfBounds)?;
+        if end > self.len {
+            return Err(ResolveError::OutOfBounds);
+        }
+        // SAFETY: `self.base` is valid for `self.len` bytes per `SharedRegion::new`'s
+        // contract, and `ptr.offset` is within that range per the check above.
+        let addr = unsafe { self.base.add(ptr.offset) } as *const T;
+        if !(addr as usize).is_multiple_of(mem::align_of::<T>()) {
+            return Err(ResolveError::Misaligned);
+        }
+        Ok(addr)
+    }
+
+    /// Resolves `ptr` into a raw mutable pointer within this region, bounds- and
+    /// alignment-checking it first.
+    pub fn resolve_mut<T>(&self, ptr: ProcPtr<T>) -> Result<*mut T, ResolveError> {
+        self.resolve(ptr).map(|p| p as *mut T)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(align(8))]
+    struct AlignedBuf([u8; 32]);
+
+    #[test]
+    fn offset_of_and_resolve_round_trip() {
+        let mut buf = AlignedBuf([0u8; 32]);
+        let base = buf.0.as_mut_ptr();
+        let value_ref: &u32 = unsafe { &*(base.add(8) as *const u32) };
+
+        let region = unsafe { SharedRegion::new(base, buf.0.len()) };
+        let ptr = region.offset_of(value_ref);
+        assert_eq!(ptr.offset(), 8);
+
+        assert_eq!(region.resolve(ptr), Ok(value_ref as *const u32));
+        assert_eq!(region.resolve_mut(ptr), Ok(value_ref as *const u32 as *mut u32));
+    }
+
+    #[test]
+    fn resolve_rejects_out_of_bounds_offsets() {
+        let mut buf = AlignedBuf([0u8; 32]);
+        let base = buf.0.as_mut_ptr();
+        let value_ref: &u32 = unsafe { &*(base.add(8) as *const u32) };
+
+        let region = unsafe { SharedRegion::new(base, buf.0.len()) };
+        let ptr = region.offset_of(value_ref);
+
+        let too_small_region = unsafe { SharedRegion::new(base, 8) };
+        assert_eq!(too_small_region.resolve(ptr), Err(ResolveError::OutOfBounds));
+    }
+
+    #[test]
+    fn resolve_rejects_misaligned_offsets() {
+        let mut buf = AlignedBuf([0u8; 32]);
+        let base = buf.0.as_mut_ptr();
+        let region = unsafe { SharedRegion::new(base, buf.0.len()) };
+
+        // `ProcPtr`'s fields are private, but this test module is nested inside the same
+        // file, so it can still build one with an offset that doesn't occur naturally
+        // through `offset_of` (which only ever hands back aligned offsets of real values).
+        let misaligned = ProcPtr::<u32> {
+            offset: 1,
+            _marker: PhantomData,
+        };
+        assert_eq!(region.resolve(misaligned), Err(ResolveError::Misaligned));
+    }
+
+    #[test]
+    #[should_panic]
+    fn offset_of_panics_for_a_value_outside_the_region() {
+        let mut buf = AlignedBuf([0u8; 32]);
+        let region = unsafe { SharedRegion::new(buf.0.as_mut_ptr(), buf.0.len()) };
+        let outside = 0u32;
+        region.offset_of(&outside);
+    }
+}